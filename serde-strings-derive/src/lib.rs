@@ -0,0 +1,244 @@
+// serde-strings
+// Copyright (C) SOFe
+//
+// Licensed under the Apache License, Version 2.0 (the License);
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an AS IS BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Derive macro implementation backing `serde_strings::SerdeStrEnum`. Not meant to be used
+//! directly; depend on the `serde-strings` crate and its re-export instead.
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Ident, LitStr};
+
+/// Generates `Display` and `FromStr` impls mapping unit variants to configured string labels.
+#[proc_macro_derive(SerdeStrEnum, attributes(serde_str))]
+pub fn derive_serde_str_enum(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+struct Variant {
+    ident: Ident,
+    label: String,
+}
+
+/// Checks whether `ty` is (a path ending in) `String`, e.g. `String` or `std::string::String`.
+fn is_string_type(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "String"),
+        _ => false,
+    }
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let enum_ident = &input.ident;
+    let data = match &input.data {
+        Data::Enum(data) => data,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input,
+                "SerdeStrEnum can only be derived for enums",
+            ))
+        }
+    };
+
+    let mut ignore_case = false;
+    for attr in &input.attrs {
+        if attr.path().is_ident("serde_str") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("ignore_case") {
+                    ignore_case = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported serde_str container attribute"))
+                }
+            })?;
+        }
+    }
+
+    let mut variants = Vec::new();
+    let mut other: Option<Ident> = None;
+
+    for variant in &data.variants {
+        let mut label = variant.ident.to_string();
+        let mut is_other = false;
+
+        for attr in &variant.attrs {
+            if attr.path().is_ident("serde_str") {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("rename") {
+                        let value: LitStr = meta.value()?.parse()?;
+                        label = value.value();
+                        Ok(())
+                    } else if meta.path.is_ident("other") {
+                        is_other = true;
+                        Ok(())
+                    } else {
+                        Err(meta.error("unsupported serde_str variant attribute"))
+                    }
+                })?;
+            }
+        }
+
+        if is_other {
+            if other.is_some() {
+                return Err(syn::Error::new_spanned(
+                    variant,
+                    "only one #[serde_str(other)] variant is allowed",
+                ));
+            }
+            let field = match &variant.fields {
+                Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0],
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        &variant.fields,
+                        "#[serde_str(other)] variant must hold a single String field",
+                    ))
+                }
+            };
+            if !is_string_type(&field.ty) {
+                return Err(syn::Error::new_spanned(
+                    &field.ty,
+                    "#[serde_str(other)] variant's field must be of type `String`",
+                ));
+            }
+            other = Some(variant.ident.clone());
+            continue;
+        }
+
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new_spanned(
+                &variant.fields,
+                "SerdeStrEnum only supports unit variants (plus one #[serde_str(other)] catch-all)",
+            ));
+        }
+
+        if let Some(dup) = variants.iter().find(|v: &&Variant| {
+            if ignore_case {
+                v.label.eq_ignore_ascii_case(&label)
+            } else {
+                v.label == label
+            }
+        }) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                format!(
+                    "duplicate serde_str label {:?} (also used by variant `{}`); each variant must have a distinct label",
+                    label, dup.ident
+                ),
+            ));
+        }
+
+        variants.push(Variant {
+            ident: variant.ident.clone(),
+            label,
+        });
+    }
+
+    let display_arms = variants.iter().map(|v| {
+        let ident = &v.ident;
+        let label = &v.label;
+        quote! { #enum_ident::#ident => f.write_str(#label), }
+    });
+    let display_other_arm = other.as_ref().map(|ident| {
+        quote! { #enum_ident::#ident(s) => f.write_str(s), }
+    });
+
+    let from_str_checks = variants.iter().map(|v| {
+        let ident = &v.ident;
+        let label = &v.label;
+        let cond = if ignore_case {
+            quote! { s.eq_ignore_ascii_case(#label) }
+        } else {
+            quote! { s == #label }
+        };
+        quote! {
+            if #cond {
+                return ::std::result::Result::Ok(#enum_ident::#ident);
+            }
+        }
+    });
+    let error_ident = format_ident!("{}ParseError", enum_ident);
+    let enum_name = enum_ident.to_string();
+    let valid_labels = variants
+        .iter()
+        .map(|v| v.label.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let from_str_tail = match &other {
+        Some(ident) => quote! {
+            ::std::result::Result::Ok(#enum_ident::#ident(s.to_string()))
+        },
+        None => quote! {
+            ::std::result::Result::Err(#error_ident::new(s))
+        },
+    };
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl ::std::fmt::Display for #enum_ident {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                match self {
+                    #(#display_arms)*
+                    #display_other_arm
+                }
+            }
+        }
+
+        #[doc = "Error returned when parsing a string into this enum fails."]
+        #[derive(Debug, Clone)]
+        pub struct #error_ident {
+            input: ::std::string::String,
+        }
+
+        impl #error_ident {
+            fn new(input: &str) -> Self {
+                Self { input: input.to_string() }
+            }
+        }
+
+        #[automatically_derived]
+        impl ::std::fmt::Display for #error_ident {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                write!(
+                    f,
+                    "invalid value {:?} for `{}`, expected one of: {}",
+                    self.input, #enum_name, #valid_labels,
+                )
+            }
+        }
+
+        #[automatically_derived]
+        impl ::std::error::Error for #error_ident {}
+
+        #[automatically_derived]
+        impl ::std::str::FromStr for #enum_ident {
+            type Err = #error_ident;
+
+            fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+                #(#from_str_checks)*
+                #from_str_tail
+            }
+        }
+    })
+}
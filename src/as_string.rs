@@ -0,0 +1,257 @@
+// serde-strings
+// Copyright (C) SOFe
+//
+// Licensed under the Apache License, Version 2.0 (the License);
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an AS IS BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `#[serde(with = "serde_strings::as_string")]` helpers for stringifying a `Display`/`FromStr`
+//! field in place, without changing its type to [`SerdeStr`](crate::SerdeStr).
+//!
+//! Like `#[serde(untagged)]` and other `deserialize_any`-based serde helpers, [`deserialize`] (and
+//! therefore [`option::deserialize`]) requires a self-describing `Deserializer` and fails on
+//! non-self-describing formats such as `bincode` or `postcard`, which don't support
+//! `deserialize_any`.
+
+use std::fmt;
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use serde::de::{Error, Visitor};
+use serde::{Serialize, Serializer};
+
+pub(crate) use de::FromStrVisitor;
+
+/// Serializes `value` using its `Display` implementation.
+pub fn serialize<T, S>(value: &T, ser: S) -> Result<S::Ok, S::Error>
+where
+    T: Display,
+    S: Serializer,
+{
+    value.to_string().serialize(ser)
+}
+
+/// Deserializes a value by parsing it through `FromStr`.
+///
+/// Unlike `String::deserialize(de)?.parse()`, this borrows the input string instead of
+/// allocating one, and also accepts numbers/booleans by formatting them before parsing, so both
+/// `{"port":8080}` and `{"port":"8080"}` deserialize successfully.
+///
+/// This calls `deserialize_any` rather than `deserialize_str`: self-describing formats such as
+/// `serde_json` reject non-string input outright when asked for a `str`, so only `deserialize_any`
+/// actually reaches the `visit_u64`/`visit_bool`/etc. fallbacks below.
+///
+/// Because of that, this requires a self-describing `Deserializer` and errors (e.g.
+/// `DeserializeAnyNotSupported`) on non-self-describing formats such as `bincode` or `postcard`.
+pub fn deserialize<'de, T, D>(de: D) -> Result<T, D::Error>
+where
+    T: FromStr,
+    T::Err: Display,
+    D: serde::Deserializer<'de>,
+{
+    de.deserialize_any(FromStrVisitor(PhantomData))
+}
+
+mod de {
+    use super::*;
+
+    pub(crate) struct FromStrVisitor<T>(pub(crate) PhantomData<T>);
+
+    impl<'de, T> Visitor<'de> for FromStrVisitor<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        type Value = T;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "a string or scalar parseable via FromStr")
+        }
+
+        fn visit_str<E: Error>(self, s: &str) -> Result<T, E> {
+            s.parse().map_err(Error::custom)
+        }
+
+        fn visit_borrowed_str<E: Error>(self, s: &'de str) -> Result<T, E> {
+            s.parse().map_err(Error::custom)
+        }
+
+        fn visit_string<E: Error>(self, s: String) -> Result<T, E> {
+            self.visit_str(&s)
+        }
+
+        fn visit_bool<E: Error>(self, v: bool) -> Result<T, E> {
+            self.visit_str(if v { "true" } else { "false" })
+        }
+
+        fn visit_u64<E: Error>(self, v: u64) -> Result<T, E> {
+            self.visit_str(&v.to_string())
+        }
+
+        fn visit_i64<E: Error>(self, v: i64) -> Result<T, E> {
+            self.visit_str(&v.to_string())
+        }
+
+        fn visit_f64<E: Error>(self, v: f64) -> Result<T, E> {
+            self.visit_str(&v.to_string())
+        }
+    }
+}
+
+/// `#[serde(with = "serde_strings::as_string::option")]` helpers for `Option<T>` fields: `None`
+/// stays `null`, and `Some(v)` is stringified the same way as [`super`].
+pub mod option {
+    use std::fmt;
+    use std::fmt::Display;
+    use std::marker::PhantomData;
+    use std::str::FromStr;
+
+    use serde::de::Visitor;
+    use serde::{Deserializer, Serialize, Serializer};
+
+    use super::FromStrVisitor;
+
+    /// Serializes `value` as `null` or the stringified inner value.
+    pub fn serialize<T, S>(value: &Option<T>, ser: S) -> Result<S::Ok, S::Error>
+    where
+        T: Display,
+        S: Serializer,
+    {
+        value.as_ref().map(ToString::to_string).serialize(ser)
+    }
+
+    /// Deserializes `null` as `None`, or a string/scalar as `Some` the same way as [`super`].
+    ///
+    /// Like [`super::deserialize`], this requires a self-describing `Deserializer` and errors on
+    /// non-self-describing formats such as `bincode` or `postcard`.
+    pub fn deserialize<'de, T, D>(de: D) -> Result<Option<T>, D::Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+        D: Deserializer<'de>,
+    {
+        de.deserialize_option(OptionVisitor(PhantomData))
+    }
+
+    struct OptionVisitor<T>(PhantomData<T>);
+
+    impl<'de, T> Visitor<'de> for OptionVisitor<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        type Value = Option<T>;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "null or a string or scalar parseable via FromStr")
+        }
+
+        fn visit_none<E: serde::de::Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_unit<E: serde::de::Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_some<D: Deserializer<'de>>(self, de: D) -> Result<Self::Value, D::Error> {
+            de.deserialize_any(FromStrVisitor(PhantomData)).map(Some)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_de {
+    use serde_derive::Deserialize;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Schema {
+        #[serde(with = "crate::as_string")]
+        port: u16,
+        #[serde(with = "crate::as_string::option")]
+        alt_port: Option<u16>,
+    }
+
+    #[test]
+    fn test_parse() {
+        let json = r##"{"port":"8080","alt_port":"443"}"##;
+        let parsed: Schema = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            parsed,
+            Schema {
+                port: 8080,
+                alt_port: Some(443),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_scalar() {
+        let json = r##"{"port":8080,"alt_port":null}"##;
+        let parsed: Schema = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            parsed,
+            Schema {
+                port: 8080,
+                alt_port: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_some_scalar() {
+        let json = r##"{"port":"8080","alt_port":443}"##;
+        let parsed: Schema = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            parsed,
+            Schema {
+                port: 8080,
+                alt_port: Some(443),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_none() {
+        let json = r##"{"port":"8080","alt_port":null}"##;
+        let parsed: Schema = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            parsed,
+            Schema {
+                port: 8080,
+                alt_port: None,
+            }
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_ser {
+    use serde_derive::Serialize;
+
+    #[derive(Debug, Serialize)]
+    struct Schema {
+        #[serde(with = "crate::as_string")]
+        port: u16,
+        #[serde(with = "crate::as_string::option")]
+        alt_port: Option<u16>,
+    }
+
+    #[test]
+    fn test_display() {
+        let json = serde_json::to_string(&Schema {
+            port: 8080,
+            alt_port: None,
+        });
+        assert_eq!(json.unwrap(), r##"{"port":"8080","alt_port":null}"##);
+    }
+}
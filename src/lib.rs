@@ -13,13 +13,58 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+//! Deserializing `SerdeStr`, `SerdeStrVec`, `SerdeStrMap`, the [`as_string`] `with`-module and
+//! `SerdeStrOrNative` all accept non-string scalars (numbers/booleans) in addition to strings,
+//! which requires calling the `Deserializer`'s `deserialize_any`. Like other
+//! `deserialize_any`-based serde helpers (e.g. `#[serde(untagged)]`), this means deserializing any
+//! of them requires a self-describing format and errors on non-self-describing formats such as
+//! `bincode` or `postcard`.
+
+use std::collections::HashMap;
 use std::fmt::Display;
+use std::hash::Hash;
 use std::str::FromStr;
 
-use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+use serde::ser::SerializeSeq;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+pub mod as_string;
+
+/// Derives `Display` and `FromStr` for an enum of unit variants, mapping each variant to an
+/// explicit on-the-wire label so it composes directly with [`SerdeStr`] (or [`as_string`]):
+///
+/// ```ignore
+/// #[derive(SerdeStrEnum)]
+/// enum Color {
+///     #[serde_str(rename = "red")]
+///     Red,
+///     #[serde_str(rename = "blue")]
+///     Blue,
+///     /// Captures any other input instead of failing to parse.
+///     #[serde_str(other)]
+///     Other(String),
+/// }
+/// ```
+///
+/// - `#[serde_str(rename = "...")]` on a variant sets its label (defaults to the variant name).
+/// - `#[serde_str(other)]` marks a single-`String`-field catch-all variant for unrecognized
+///   input; at most one variant may be marked `other`.
+/// - `#[serde_str(ignore_case)]` on the enum itself makes `FromStr` match labels
+///   ASCII-case-insensitively; non-ASCII characters must match exactly.
+///
+/// The generated `FromStr::Err` is a dedicated error type (named `<Enum>ParseError`) whose
+/// `Display` prints the offending input and the list of valid labels.
+pub use serde_strings_derive::SerdeStrEnum;
 
 /// Wraps a value Display and/or FromStr value to be used as a field in a derive(Serialize) or
 /// derive(Deserialize) struct/enum.
+///
+/// Prefer the [`as_string`] `with`-module instead if the field doesn't need to change type, e.g.
+/// `#[serde(with = "serde_strings::as_string")] port: u16`. `Option<SerdeStr<T>>` also composes
+/// directly if a type-changing `Option` field is acceptable.
+///
+/// Deserializing requires a self-describing `Deserializer` and errors on non-self-describing
+/// formats such as `bincode` or `postcard`; see the crate-level docs.
 #[derive(Clone, Copy, Debug, Default, Hash, Eq, PartialEq, Ord, PartialOrd)]
 pub struct SerdeStr<T> {
     /// The inner value
@@ -71,9 +116,7 @@ where
         D: Deserializer<'de>,
     {
         Ok(Self {
-            value: String::deserialize(de)?
-                .parse()
-                .map_err(|err| Error::custom(err))?,
+            value: as_string::deserialize(de)?,
         })
     }
 }
@@ -87,7 +130,280 @@ where
     where
         S: Serializer,
     {
-        self.value.to_string().serialize(ser)
+        as_string::serialize(&self.value, ser)
+    }
+}
+
+/// Wraps a `Vec<T>` so each element is serialized/deserialized through `Display`/`FromStr`,
+/// e.g. `Vec<IpAddr>` as `["127.0.0.1", "::1"]`.
+///
+/// Deserializing requires a self-describing `Deserializer` and errors on non-self-describing
+/// formats such as `bincode` or `postcard`; see the crate-level docs.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SerdeStrVec<T> {
+    /// The inner values
+    pub values: Vec<T>,
+}
+
+impl<T> SerdeStrVec<T> {
+    /// Gets a reference of the inner values.
+    #[inline]
+    pub fn values(&self) -> &Vec<T> {
+        &self.values
+    }
+
+    /// Gets a mutable reference of the inner values.
+    #[inline]
+    pub fn values_mut(&mut self) -> &mut Vec<T> {
+        &mut self.values
+    }
+
+    /// Sets the inner values.
+    #[inline]
+    pub fn set_values(&mut self, values: Vec<T>) {
+        self.values = values;
+    }
+
+    /// Moves out the inner values.
+    #[inline]
+    pub fn unwrap(self) -> Vec<T> {
+        self.values
+    }
+}
+
+/// Creates a SerdeStrVec from its inner values.
+impl<T> From<Vec<T>> for SerdeStrVec<T> {
+    #[inline]
+    fn from(values: Vec<T>) -> Self {
+        Self { values }
+    }
+}
+
+impl<'de, T> Deserialize<'de> for SerdeStrVec<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    #[inline]
+    fn deserialize<D>(de: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let values = Vec::<SerdeStr<T>>::deserialize(de)?
+            .into_iter()
+            .map(SerdeStr::unwrap)
+            .collect();
+        Ok(Self { values })
+    }
+}
+
+impl<T> Serialize for SerdeStrVec<T>
+where
+    T: Display,
+{
+    #[inline]
+    fn serialize<S>(&self, ser: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = ser.serialize_seq(Some(self.values.len()))?;
+        for value in &self.values {
+            seq.serialize_element(&value.to_string())?;
+        }
+        seq.end()
+    }
+}
+
+/// Wraps a `HashMap<K, V>` so both keys and values are serialized/deserialized through
+/// `Display`/`FromStr`. Useful for formats such as JSON, urlencoded query strings or TOML tables
+/// that require string keys, so a normally-numeric `K` can still be used as a map key.
+///
+/// Deserializing requires a self-describing `Deserializer` and errors on non-self-describing
+/// formats such as `bincode` or `postcard`; see the crate-level docs.
+#[derive(Clone, Debug, Default)]
+pub struct SerdeStrMap<K, V> {
+    /// The inner map
+    pub map: HashMap<K, V>,
+}
+
+impl<K: Eq + Hash, V: PartialEq> PartialEq for SerdeStrMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.map == other.map
+    }
+}
+
+impl<K, V> SerdeStrMap<K, V> {
+    /// Gets a reference of the inner map.
+    #[inline]
+    pub fn map(&self) -> &HashMap<K, V> {
+        &self.map
+    }
+
+    /// Gets a mutable reference of the inner map.
+    #[inline]
+    pub fn map_mut(&mut self) -> &mut HashMap<K, V> {
+        &mut self.map
+    }
+
+    /// Sets the inner map.
+    #[inline]
+    pub fn set_map(&mut self, map: HashMap<K, V>) {
+        self.map = map;
+    }
+
+    /// Moves out the inner map.
+    #[inline]
+    pub fn unwrap(self) -> HashMap<K, V> {
+        self.map
+    }
+}
+
+/// Creates a SerdeStrMap from its inner map.
+impl<K, V> From<HashMap<K, V>> for SerdeStrMap<K, V> {
+    #[inline]
+    fn from(map: HashMap<K, V>) -> Self {
+        Self { map }
+    }
+}
+
+impl<'de, K, V> Deserialize<'de> for SerdeStrMap<K, V>
+where
+    K: FromStr + Eq + Hash,
+    K::Err: Display,
+    V: FromStr,
+    V::Err: Display,
+{
+    #[inline]
+    fn deserialize<D>(de: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let map = HashMap::<SerdeStr<K>, SerdeStr<V>>::deserialize(de)?
+            .into_iter()
+            .map(|(k, v)| (k.unwrap(), v.unwrap()))
+            .collect();
+        Ok(Self { map })
+    }
+}
+
+impl<K, V> Serialize for SerdeStrMap<K, V>
+where
+    K: Display,
+    V: Display,
+{
+    #[inline]
+    fn serialize<S>(&self, ser: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        ser.collect_map(
+            self.map
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string())),
+        )
+    }
+}
+
+/// Wraps a value that deserializes from either its native serde representation or, as a
+/// fallback, a string parsed through `FromStr`. Lets a single schema tolerate both
+/// `{"timeout":30}` (native `T: Deserialize`) and `{"timeout":"30s"}` (`FromStr`).
+///
+/// Serialization prefers the native `T: Serialize` form; set `force_string` to opt into always
+/// emitting the `Display` form instead.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct SerdeStrOrNative<T> {
+    /// The inner value
+    pub value: T,
+    /// When set, serialization emits the stringified form instead of the native one.
+    pub force_string: bool,
+}
+
+impl<T> SerdeStrOrNative<T> {
+    /// Gets a reference of the inner value.
+    #[inline]
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Gets a mutable reference of the inner value.
+    #[inline]
+    pub fn value_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+
+    /// Sets the inner value.
+    #[inline]
+    pub fn set_value(&mut self, t: T) {
+        self.value = t;
+    }
+
+    /// Moves out the inner value.
+    #[inline]
+    pub fn unwrap(self) -> T {
+        self.value
+    }
+}
+
+/// Creates a SerdeStrOrNative from its inner value, preferring the native form on serialize.
+impl<T> From<T> for SerdeStrOrNative<T> {
+    #[inline]
+    fn from(value: T) -> Self {
+        Self {
+            value,
+            force_string: false,
+        }
+    }
+}
+
+/// Buffers the input so it can be retried as `T`'s native representation and, on failure, as a
+/// string. `#[serde(untagged)]` makes `serde_derive` generate the necessary buffering glue (it
+/// has access to serde's internal `Content` type, which isn't public API for hand-written code).
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum NativeOrString<T> {
+    Native(T),
+    String(String),
+}
+
+impl<'de, T> Deserialize<'de> for SerdeStrOrNative<T>
+where
+    T: Deserialize<'de> + FromStr,
+    T::Err: Display,
+{
+    fn deserialize<D>(de: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let repr = NativeOrString::<T>::deserialize(de).map_err(|_| {
+            serde::de::Error::custom(
+                "value matched neither the native representation nor a string form",
+            )
+        })?;
+        let value = match repr {
+            NativeOrString::Native(value) => value,
+            NativeOrString::String(s) => s.parse().map_err(serde::de::Error::custom)?,
+        };
+        Ok(Self {
+            value,
+            force_string: false,
+        })
+    }
+}
+
+impl<T> Serialize for SerdeStrOrNative<T>
+where
+    T: Serialize + Display,
+{
+    #[inline]
+    fn serialize<S>(&self, ser: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if self.force_string {
+            as_string::serialize(&self.value, ser)
+        } else {
+            self.value.serialize(ser)
+        }
     }
 }
 
@@ -97,7 +413,7 @@ mod test_de {
 
     use serde_derive::Deserialize;
 
-    use crate::SerdeStr;
+    use crate::{SerdeStr, SerdeStrMap, SerdeStrOrNative, SerdeStrVec};
 
     #[derive(Debug, PartialEq)]
     struct IsParsed(i32);
@@ -128,6 +444,47 @@ mod test_de {
             }
         );
     }
+
+    #[test]
+    fn test_parse_vec() {
+        let json = r##"["a","bb","ccc"]"##;
+        let parsed = serde_json::from_str::<SerdeStrVec<IsParsed>>(json);
+        assert_eq!(
+            parsed.unwrap(),
+            SerdeStrVec {
+                values: vec![IsParsed(1), IsParsed(2), IsParsed(3)]
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_map() {
+        let json = r##"{"1":"a","22":"bb"}"##;
+        let parsed = serde_json::from_str::<SerdeStrMap<u16, IsParsed>>(json).unwrap();
+        assert_eq!(parsed.map.get(&1), Some(&IsParsed(1)));
+        assert_eq!(parsed.map.get(&22), Some(&IsParsed(2)));
+    }
+
+    #[test]
+    fn test_parse_native() {
+        let parsed = serde_json::from_str::<SerdeStrOrNative<u32>>("30").unwrap();
+        assert_eq!(parsed.value, 30);
+        assert!(!parsed.force_string);
+    }
+
+    #[test]
+    fn test_parse_fallback_string() {
+        let parsed = serde_json::from_str::<SerdeStrOrNative<u32>>(r##""30""##).unwrap();
+        assert_eq!(parsed.value, 30);
+        assert!(!parsed.force_string);
+    }
+
+    #[test]
+    fn test_parse_neither_form_matches() {
+        let err = serde_json::from_str::<SerdeStrOrNative<u32>>("true").unwrap_err();
+        let message = err.to_string();
+        assert!(!message.contains("NativeOrString"), "{message}");
+    }
 }
 
 #[cfg(test)]
@@ -136,7 +493,9 @@ mod test_ser {
 
     use serde_derive::Serialize;
 
-    use crate::SerdeStr;
+    use std::collections::HashMap;
+
+    use crate::{SerdeStr, SerdeStrMap, SerdeStrOrNative, SerdeStrVec};
 
     #[derive(Debug)]
     struct CanDisplay(&'static str);
@@ -161,4 +520,90 @@ mod test_ser {
         });
         assert_eq!(json.unwrap(), r##"{"data":"3"}"##);
     }
+
+    #[test]
+    fn test_display_vec() {
+        let json = serde_json::to_string(&SerdeStrVec {
+            values: vec![CanDisplay("a"), CanDisplay("bb")],
+        });
+        assert_eq!(json.unwrap(), r##"["1","2"]"##);
+    }
+
+    #[test]
+    fn test_display_map() {
+        let mut map = HashMap::new();
+        map.insert(1u16, CanDisplay("a"));
+        let json = serde_json::to_string(&SerdeStrMap { map });
+        assert_eq!(json.unwrap(), r##"{"1":"1"}"##);
+    }
+
+    #[test]
+    fn test_display_native() {
+        let json = serde_json::to_string(&SerdeStrOrNative::from(30u32));
+        assert_eq!(json.unwrap(), "30");
+    }
+
+    #[test]
+    fn test_display_force_string() {
+        let json = serde_json::to_string(&SerdeStrOrNative {
+            value: 30u32,
+            force_string: true,
+        });
+        assert_eq!(json.unwrap(), r##""30""##);
+    }
+}
+
+#[cfg(test)]
+mod test_enum {
+    use std::str::FromStr;
+
+    use crate::SerdeStrEnum;
+
+    #[derive(Debug, SerdeStrEnum, PartialEq)]
+    enum Color {
+        #[serde_str(rename = "red")]
+        Red,
+        #[serde_str(rename = "blue")]
+        Blue,
+        #[serde_str(other)]
+        Other(String),
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Color::Red.to_string(), "red");
+        assert_eq!(Color::Other("teal".to_string()).to_string(), "teal");
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(Color::from_str("red").unwrap(), Color::Red);
+        assert_eq!(
+            Color::from_str("teal").unwrap(),
+            Color::Other("teal".to_string())
+        );
+    }
+
+    #[derive(Debug, SerdeStrEnum, PartialEq)]
+    #[serde_str(ignore_case)]
+    enum Level {
+        #[serde_str(rename = "low")]
+        Low,
+        #[serde_str(rename = "high")]
+        High,
+    }
+
+    #[test]
+    fn test_ignore_case() {
+        assert_eq!(Level::from_str("LOW").unwrap(), Level::Low);
+    }
+
+    #[test]
+    fn test_unrecognized_error() {
+        let err = Level::from_str("medium").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid value \"medium\" for `Level`, expected one of: low, high"
+        );
+    }
 }